@@ -24,10 +24,18 @@ fn main() {
         while let Some(event) = host.service().unwrap() {
             match event {
                 enet::Event::Connect { peer, .. } => {
-                    println!("Peer {} connected", peer.id().0);
+                    println!(
+                        "Peer {} connected (slot {})",
+                        peer.id().generation(),
+                        peer.id().index()
+                    );
                 }
                 enet::Event::Disconnect { peer, .. } => {
-                    println!("Peer {} disconnected", peer.id().0);
+                    println!(
+                        "Peer {} disconnected (slot {})",
+                        peer.id().generation(),
+                        peer.id().index()
+                    );
                 }
                 enet::Event::Receive {
                     peer,
@@ -39,6 +47,10 @@ fn main() {
                     }
                     _ = peer.send(channel_id, &packet);
                 }
+                enet::Event::PeerDiscovered { address } => {
+                    println!("Learned about peer at {address}, dialing");
+                    _ = host.connect(address, 2, 0);
+                }
             }
         }
         std::thread::sleep(Duration::from_millis(10));
@@ -1,4 +1,4 @@
-use crate::{Packet, Peer, PeerID, Socket};
+use crate::{Address, Packet, Peer, PeerID, Socket};
 
 /// An ENet event returned by [`Host::service`](`crate::Host::service`).
 #[derive(Debug)]
@@ -26,12 +26,19 @@ pub enum Event<'a, S: Socket> {
         /// The actual packet data.
         packet: Packet,
     },
+    /// The [peer exchange](`crate::pex`) subsystem learned of a peer address we didn't
+    /// already know about.
+    PeerDiscovered {
+        /// The newly-learned address. Pass it to [`Host::connect`](`crate::Host::connect`)
+        /// to dial it.
+        address: S::Address,
+    },
 }
 
 impl<'a, S: Socket> Event<'a, S> {
     /// Remove the peer reference from this event, converting into an [`EventNoRef`].
     #[must_use]
-    pub fn no_ref(self) -> EventNoRef {
+    pub fn no_ref(self) -> EventNoRef<S::Address> {
         match self {
             Self::Connect { peer, data } => EventNoRef::Connect {
                 peer: peer.id(),
@@ -50,6 +57,7 @@ impl<'a, S: Socket> Event<'a, S> {
                 channel_id,
                 packet,
             },
+            Self::PeerDiscovered { address } => EventNoRef::PeerDiscovered { address },
         }
     }
 }
@@ -58,7 +66,7 @@ impl<'a, S: Socket> Event<'a, S> {
 ///
 /// Acquired with [`Event::no_ref`].
 #[derive(Debug, Clone)]
-pub enum EventNoRef {
+pub enum EventNoRef<A: Address> {
     /// A new peer has connected.
     Connect {
         /// Peer that generated the event.
@@ -82,4 +90,11 @@ pub enum EventNoRef {
         /// The actual packet data.
         packet: Packet,
     },
+    /// The [peer exchange](`crate::pex`) subsystem learned of a peer address we didn't
+    /// already know about.
+    PeerDiscovered {
+        /// The newly-learned address. Pass it to [`Host::connect`](`crate::Host::connect`)
+        /// to dial it.
+        address: A,
+    },
 }
@@ -0,0 +1,73 @@
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// Uniquely identifies a [`Peer`](`crate::Peer`) for the lifetime of a
+/// [`Host`](`crate::Host`).
+///
+/// A `PeerID` pairs the peer's compact slot index, used internally for routing into the
+/// host's peer array, with a strictly-incrementing `u64` generation, minted by
+/// [`PeerIdGenerator`] for every successful connect a [`Host`](`crate::Host`) accepts. The
+/// slot index alone is reused as soon as a peer disconnects and a new peer takes its place,
+/// so it is not safe to key application state by index across reconnects; the generation
+/// makes the combined `PeerID` unique for the life of the process even if the same address
+/// reconnects into the same slot — as long as every slot assignment goes through the same
+/// [`PeerIdGenerator`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PeerID {
+    pub(crate) index: usize,
+    pub(crate) generation: u64,
+}
+
+impl PeerID {
+    #[must_use]
+    pub(crate) fn new(index: usize, generation: u64) -> Self {
+        Self { index, generation }
+    }
+
+    /// The compact slot index used for internal peer-array lookups.
+    ///
+    /// Do not use this alone as a stable application-level key: it is reused as soon as the
+    /// peer disconnects. Compare whole [`PeerID`] values, or use [`PeerID::generation`] for a
+    /// value that only ever increases, instead.
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The strictly-incrementing, process-lifetime-unique generation issued when this peer
+    /// connected.
+    #[must_use]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
+/// Issues the strictly-incrementing, process-lifetime-unique generation to pair with a
+/// peer's slot index on every successful connect.
+///
+/// A [`Host`](`crate::Host`) must own exactly one `PeerIdGenerator` for its whole lifetime
+/// and call [`PeerIdGenerator::next`] exactly once per accepted connection, at the point
+/// where it assigns the peer its slot index, so that a [`PeerID`] handed out for that slot
+/// can never alias a later connection that reuses the same slot: the generation only ever
+/// goes up, even across reconnects from the same address. Sharing a generator across slot
+/// assignments inconsistently, or minting more than one `PeerID` per connect, breaks the
+/// uniqueness guarantee this type exists to provide.
+#[derive(Debug, Default)]
+pub struct PeerIdGenerator {
+    next_generation: AtomicU64,
+}
+
+impl PeerIdGenerator {
+    /// Create a generator starting from generation `0`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mint the [`PeerID`] for a peer that just connected into slot `index`, advancing the
+    /// generation counter so it is never reused.
+    #[must_use]
+    pub fn next(&self, index: usize) -> PeerID {
+        let generation = self.next_generation.fetch_add(1, Ordering::Relaxed);
+        PeerID::new(index, generation)
+    }
+}
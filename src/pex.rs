@@ -0,0 +1,208 @@
+//! Peer exchange (PEX): an optional gossip layer that lets connected peers advertise
+//! addresses of other peers they know about, so a new node can bootstrap a mesh from a
+//! single seed instead of only ever forming a star topology.
+//!
+//! PEX messages travel over a reserved channel chosen by the caller of
+//! `Reactor::spawn_with_pex`; packets on that channel are consumed by peer exchange and are
+//! not delivered to the application. Learned addresses surface from that reactor as
+//! [`EventNoRef::PeerDiscovered`](`crate::EventNoRef::PeerDiscovered`), leaving the decision
+//! of whether (and how) to dial them to the application.
+//! [`Host::service`](`crate::Host::service`) itself does not return
+//! [`Event::PeerDiscovered`](`crate::Event::PeerDiscovered`).
+
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use crate::{Address, PeerID};
+
+const TAG_GET_PEERS: u8 = 0;
+const TAG_PEERS: u8 = 1;
+const ATYP_IPV4: u8 = 4;
+const ATYP_IPV6: u8 = 6;
+
+/// The two messages peer exchange sends over its reserved control channel.
+#[derive(Debug, Clone)]
+pub enum PexMessage<A: Address> {
+    /// Ask the receiving peer to share addresses it knows about.
+    GetPeers,
+    /// A capped list of addresses the sender knows about, in response to
+    /// [`PexMessage::GetPeers`] or sent unsolicited on its own schedule.
+    Peers(Vec<A>),
+}
+
+/// Tunable limits for the peer exchange subsystem.
+#[derive(Debug, Clone, Copy)]
+pub struct PexConfig {
+    /// Maximum number of addresses carried in a single [`PexMessage::Peers`] message, to
+    /// bound the amplification a single request can cause.
+    pub max_addresses_per_message: usize,
+    /// Minimum number of ticks between exchanges with the same peer, to rate-limit how
+    /// often any one peer is sent a peer list. Inbound [`PexMessage::Peers`] messages are not
+    /// rate-limited.
+    pub min_ticks_between_exchanges: u32,
+}
+
+impl Default for PexConfig {
+    fn default() -> Self {
+        Self {
+            max_addresses_per_message: 32,
+            min_ticks_between_exchanges: 300,
+        }
+    }
+}
+
+/// Per-peer bookkeeping for the gossip layer: when we last exchanged with this peer, so
+/// [`PexConfig::min_ticks_between_exchanges`] can be enforced.
+#[derive(Debug, Clone, Copy, Default)]
+struct PeerPexState {
+    last_exchange_tick: Option<u32>,
+}
+
+/// Tracks known addresses and per-peer rate limits for the peer exchange gossip layer.
+///
+/// One [`Pex`] is owned by the [`Host`](`crate::Host`) when peer exchange is enabled; it
+/// decides which addresses are new enough to report and which peers are due for another
+/// round of gossip.
+#[derive(Debug, Clone)]
+pub struct Pex<A: Address> {
+    config: PexConfig,
+    known: Vec<A>,
+    peers: HashMap<PeerID, PeerPexState>,
+}
+
+impl<A: Address> Pex<A> {
+    /// Create a peer exchange tracker with the given limits.
+    #[must_use]
+    pub fn new(config: PexConfig) -> Self {
+        Self {
+            config,
+            known: Vec::new(),
+            peers: HashMap::new(),
+        }
+    }
+
+    /// Record that `address` is reachable, so it can be advertised to other peers.
+    ///
+    /// Returns `true` if this address was not already known.
+    pub fn learn(&mut self, address: A) -> bool {
+        if self.known.contains(&address) {
+            false
+        } else {
+            self.known.push(address);
+            true
+        }
+    }
+
+    /// Build the [`PexMessage::Peers`] response to advertise to `peer` at `tick`, or `None`
+    /// if `peer` was exchanged with too recently.
+    ///
+    /// `exclude` is the requesting peer's own address, so we never advertise a peer back to
+    /// itself.
+    pub fn peers_message(
+        &mut self,
+        peer: PeerID,
+        tick: u32,
+        exclude: Option<&A>,
+    ) -> Option<PexMessage<A>> {
+        if !self.due(peer, tick) {
+            return None;
+        }
+        self.peers.entry(peer).or_default().last_exchange_tick = Some(tick);
+        let addresses = self
+            .known
+            .iter()
+            .filter(|address| exclude != Some(*address))
+            .take(self.config.max_addresses_per_message)
+            .cloned()
+            .collect();
+        Some(PexMessage::Peers(addresses))
+    }
+
+    /// Whether `peer` is due for another exchange at `tick`, per
+    /// [`PexConfig::min_ticks_between_exchanges`].
+    fn due(&self, peer: PeerID, tick: u32) -> bool {
+        match self
+            .peers
+            .get(&peer)
+            .and_then(|state| state.last_exchange_tick)
+        {
+            Some(last) => tick.saturating_sub(last) >= self.config.min_ticks_between_exchanges,
+            None => true,
+        }
+    }
+
+    /// Apply a [`PexMessage::Peers`] message received from a peer, returning only the
+    /// addresses that were not already known (and are therefore newly discovered).
+    pub fn receive_peers(&mut self, addresses: Vec<A>) -> Vec<A> {
+        addresses
+            .into_iter()
+            .take(self.config.max_addresses_per_message)
+            .filter(|address| self.learn(address.clone()))
+            .collect()
+    }
+}
+
+/// Encode a [`PexMessage`] to bytes for the reserved control channel: a one-byte tag,
+/// followed for [`PexMessage::Peers`] by a big-endian `u16` count and, per address, an
+/// address-type byte (`4` or `6`), the raw address bytes, and a big-endian `u16` port.
+#[must_use]
+pub fn encode_message(message: &PexMessage<SocketAddr>) -> Vec<u8> {
+    match message {
+        PexMessage::GetPeers => vec![TAG_GET_PEERS],
+        PexMessage::Peers(addresses) => {
+            let mut bytes = vec![TAG_PEERS];
+            bytes.extend_from_slice(&(addresses.len() as u16).to_be_bytes());
+            for address in addresses {
+                match address {
+                    SocketAddr::V4(addr) => {
+                        bytes.push(ATYP_IPV4);
+                        bytes.extend_from_slice(&addr.ip().octets());
+                    }
+                    SocketAddr::V6(addr) => {
+                        bytes.push(ATYP_IPV6);
+                        bytes.extend_from_slice(&addr.ip().octets());
+                    }
+                }
+                bytes.extend_from_slice(&address.port().to_be_bytes());
+            }
+            bytes
+        }
+    }
+}
+
+/// Decode a [`PexMessage`] encoded by [`encode_message`], or `None` if `bytes` doesn't
+/// follow that wire format. Malformed peer-exchange traffic is dropped rather than trusted.
+#[must_use]
+pub fn decode_message(bytes: &[u8]) -> Option<PexMessage<SocketAddr>> {
+    match *bytes.first()? {
+        TAG_GET_PEERS => Some(PexMessage::GetPeers),
+        TAG_PEERS => {
+            let count = u16::from_be_bytes(bytes.get(1..3)?.try_into().ok()?) as usize;
+            let mut addresses = Vec::with_capacity(count);
+            let mut rest = bytes.get(3..)?;
+            for _ in 0..count {
+                let (&atyp, after_atyp) = rest.split_first()?;
+                let (address, after_address) = match atyp {
+                    ATYP_IPV4 => {
+                        let (octets, after) = after_atyp.split_at_checked(4)?;
+                        let ip = Ipv4Addr::from(<[u8; 4]>::try_from(octets).ok()?);
+                        (SocketAddr::from((ip, 0)), after)
+                    }
+                    ATYP_IPV6 => {
+                        let (octets, after) = after_atyp.split_at_checked(16)?;
+                        let ip = Ipv6Addr::from(<[u8; 16]>::try_from(octets).ok()?);
+                        (SocketAddr::from((ip, 0)), after)
+                    }
+                    _ => return None,
+                };
+                let (port, after_port) = after_address.split_at_checked(2)?;
+                let mut address = address;
+                address.set_port(u16::from_be_bytes(port.try_into().ok()?));
+                addresses.push(address);
+                rest = after_port;
+            }
+            Some(PexMessage::Peers(addresses))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,298 @@
+use std::net::SocketAddr;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::pex::{self, Pex, PexConfig, PexMessage};
+use crate::{Address, Event, EventNoRef, Host, Packet, PeerID, Socket};
+
+/// A command accepted by a [`Reactor`], applied to its owned [`Host`] from the calling
+/// thread.
+#[derive(Debug)]
+pub enum Command {
+    /// Send a packet to a peer on a channel, as with [`Peer::send`](`crate::Peer::send`).
+    Send {
+        /// Peer to send to. Commands targeting a peer that has since disconnected are
+        /// silently ignored.
+        peer: PeerID,
+        /// Channel to send on.
+        channel_id: u8,
+        /// Packet to send.
+        packet: Packet,
+    },
+    /// Send a packet to every connected peer, as with
+    /// [`Host::broadcast`](`crate::Host::broadcast`).
+    Broadcast {
+        /// Channel to send on.
+        channel_id: u8,
+        /// Packet to send.
+        packet: Packet,
+    },
+    /// Disconnect a peer, as with [`Peer::disconnect`](`crate::Peer::disconnect`).
+    Disconnect {
+        /// Peer to disconnect. Commands targeting a peer that has since disconnected are
+        /// silently ignored.
+        peer: PeerID,
+        /// Data to send along with the disconnect notification.
+        data: u32,
+    },
+}
+
+/// An item delivered over a [`Reactor`]'s event channel.
+#[derive(Debug)]
+pub enum ReactorEvent<A: Address, E> {
+    /// A normal ENet event.
+    Event(EventNoRef<A>),
+    /// [`Host::service`] returned an error; the reactor thread has stopped servicing the
+    /// host and exited after sending this.
+    ServiceError(E),
+}
+
+/// Runs a [`Host`]'s [`service`](`crate::Host::service`) loop on a dedicated thread,
+/// handing events back to the application over a channel and accepting [`Command`]s in
+/// return.
+///
+/// This lets `while let Some(event) = host.service()` move off the application's main loop:
+/// the [`Host`] is moved into the reactor thread entirely, and is only ever touched from
+/// there again.
+#[derive(Debug)]
+pub struct Reactor<S: Socket> {
+    commands: Option<Sender<Command>>,
+    events: Receiver<ReactorEvent<S::Address, S::Error>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl<S: Socket + Send + 'static> Reactor<S>
+where
+    S::Error: Send,
+{
+    /// Spawn a reactor thread that owns `host`, servicing it every `tick` while the channel
+    /// is idle.
+    #[must_use]
+    pub fn spawn(mut host: Host<S>, tick: Duration) -> Self {
+        let (command_tx, command_rx) = mpsc::channel();
+        let (event_tx, event_rx) = mpsc::channel();
+        let handle = thread::spawn(move || 'reactor: loop {
+            loop {
+                match command_rx.try_recv() {
+                    Ok(command) => Self::apply(&mut host, command),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => break 'reactor,
+                }
+            }
+            loop {
+                match host.service() {
+                    Ok(Some(event)) => {
+                        if event_tx.send(ReactorEvent::Event(event.no_ref())).is_err() {
+                            break 'reactor;
+                        }
+                    }
+                    Ok(None) => break,
+                    Err(error) => {
+                        // The host is in an unknown state after a service error; stop
+                        // touching it rather than loop on a thread the application no
+                        // longer knows is dead. Best-effort: the send can fail if nobody's
+                        // listening, but there's nothing left to do about that either way.
+                        _ = event_tx.send(ReactorEvent::ServiceError(error));
+                        break 'reactor;
+                    }
+                }
+            }
+            thread::sleep(tick);
+        });
+        Self {
+            commands: Some(command_tx),
+            events: event_rx,
+            handle: Some(handle),
+        }
+    }
+
+    fn apply(host: &mut Host<S>, command: Command) {
+        match command {
+            Command::Send {
+                peer,
+                channel_id,
+                packet,
+            } => {
+                if let Some(peer) = host.peer_mut(peer) {
+                    _ = peer.send(channel_id, &packet);
+                }
+            }
+            Command::Broadcast { channel_id, packet } => {
+                host.broadcast(channel_id, &packet);
+            }
+            Command::Disconnect { peer, data } => {
+                if let Some(peer) = host.peer_mut(peer) {
+                    peer.disconnect(data);
+                }
+            }
+        }
+    }
+
+    /// The sending half of the command channel, used to drive the owned [`Host`] from
+    /// outside the reactor thread.
+    #[must_use]
+    pub fn commands(&self) -> &Sender<Command> {
+        self.commands
+            .as_ref()
+            .expect("commands channel is only removed on drop")
+    }
+
+    /// The receiving half of the event channel. Blocks or polls like any other
+    /// [`Receiver`]. Yields a [`ReactorEvent::ServiceError`] and then closes if
+    /// [`Host::service`] ever fails.
+    #[must_use]
+    pub fn events(&self) -> &Receiver<ReactorEvent<S::Address, S::Error>> {
+        &self.events
+    }
+}
+
+impl<S: Socket<Address = SocketAddr> + Send + 'static> Reactor<S>
+where
+    S::Error: Send,
+{
+    /// Spawn a reactor thread exactly like [`Reactor::spawn`], but also drive the
+    /// [peer exchange](`crate::pex`) gossip layer over `pex_channel`: every newly-connected
+    /// peer is sent a [`PexMessage::GetPeers`], incoming peer-exchange traffic on that
+    /// channel is decoded and answered (or merged into the known address set) instead of
+    /// being forwarded to the application as a [`Receive`](`EventNoRef::Receive`) event, and
+    /// addresses learned this way surface as [`EventNoRef::PeerDiscovered`] like any other
+    /// event.
+    ///
+    /// Only available for a [`Host<S>`] addressed by [`SocketAddr`], since that's the only
+    /// address type [`pex::encode_message`]/[`pex::decode_message`] know how to serialize.
+    #[must_use]
+    pub fn spawn_with_pex(
+        mut host: Host<S>,
+        tick: Duration,
+        pex_channel: u8,
+        config: PexConfig,
+    ) -> Self {
+        let (command_tx, command_rx) = mpsc::channel();
+        let (event_tx, event_rx) = mpsc::channel();
+        let handle = thread::spawn(move || {
+            let mut pex = Pex::new(config);
+            let mut tick_count: u32 = 0;
+            'reactor: loop {
+                loop {
+                    match command_rx.try_recv() {
+                        Ok(command) => Self::apply(&mut host, command),
+                        Err(TryRecvError::Empty) => break,
+                        Err(TryRecvError::Disconnected) => break 'reactor,
+                    }
+                }
+                loop {
+                    match host.service() {
+                        Ok(Some(event)) => {
+                            let forwarded = match event {
+                                Event::Connect { peer, data } => {
+                                    pex.learn(peer.address());
+                                    let message = pex::encode_message(&PexMessage::GetPeers);
+                                    _ = peer.send(pex_channel, &Packet::reliable(&message));
+                                    Some(EventNoRef::Connect {
+                                        peer: peer.id(),
+                                        data,
+                                    })
+                                }
+                                Event::Receive {
+                                    peer,
+                                    channel_id,
+                                    packet,
+                                } if channel_id == pex_channel => {
+                                    let discovered = Self::handle_pex_packet(
+                                        &mut pex,
+                                        peer.id(),
+                                        peer.address(),
+                                        tick_count,
+                                        packet.data(),
+                                    );
+                                    if let Some(message) = discovered.response {
+                                        let bytes = pex::encode_message(&message);
+                                        _ = peer.send(pex_channel, &Packet::reliable(&bytes));
+                                    }
+                                    for address in discovered.learned {
+                                        if event_tx
+                                            .send(ReactorEvent::Event(EventNoRef::PeerDiscovered {
+                                                address,
+                                            }))
+                                            .is_err()
+                                        {
+                                            break 'reactor;
+                                        }
+                                    }
+                                    None
+                                }
+                                other => Some(other.no_ref()),
+                            };
+                            if let Some(event) = forwarded {
+                                if event_tx.send(ReactorEvent::Event(event)).is_err() {
+                                    break 'reactor;
+                                }
+                            }
+                        }
+                        Ok(None) => break,
+                        Err(error) => {
+                            _ = event_tx.send(ReactorEvent::ServiceError(error));
+                            break 'reactor;
+                        }
+                    }
+                }
+                tick_count = tick_count.wrapping_add(1);
+                thread::sleep(tick);
+            }
+        });
+        Self {
+            commands: Some(command_tx),
+            events: event_rx,
+            handle: Some(handle),
+        }
+    }
+
+    /// Decode a packet received on `pex_channel` and react to it: answer a
+    /// [`PexMessage::GetPeers`] if `peer` isn't rate-limited, or merge a
+    /// [`PexMessage::Peers`] into `pex`'s known set. Malformed packets are ignored.
+    fn handle_pex_packet(
+        pex: &mut Pex<SocketAddr>,
+        peer: PeerID,
+        peer_address: SocketAddr,
+        tick: u32,
+        data: &[u8],
+    ) -> DiscoveredPex {
+        match pex::decode_message(data) {
+            Some(PexMessage::GetPeers) => {
+                pex.learn(peer_address);
+                DiscoveredPex {
+                    response: pex.peers_message(peer, tick, Some(&peer_address)),
+                    learned: Vec::new(),
+                }
+            }
+            Some(PexMessage::Peers(addresses)) => DiscoveredPex {
+                response: None,
+                learned: pex.receive_peers(addresses),
+            },
+            None => DiscoveredPex {
+                response: None,
+                learned: Vec::new(),
+            },
+        }
+    }
+}
+
+/// The result of processing one inbound peer-exchange packet: an optional reply to send
+/// back to the sender, and any addresses newly learned as a result.
+struct DiscoveredPex {
+    response: Option<PexMessage<SocketAddr>>,
+    learned: Vec<SocketAddr>,
+}
+
+impl<S: Socket> Drop for Reactor<S> {
+    fn drop(&mut self) {
+        // Drop the command sender first so the reactor thread's `try_recv` observes
+        // `Disconnected` and exits, instead of `join` blocking on a thread that is still
+        // waiting for commands that will never come.
+        drop(self.commands.take());
+        if let Some(handle) = self.handle.take() {
+            _ = handle.join();
+        }
+    }
+}
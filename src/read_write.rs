@@ -2,6 +2,24 @@ use crate::{SocketError, VecDeque};
 
 use crate::{Address, PacketReceived, Socket, SocketOptions, Vec, MTU_MAX};
 
+/// How [`ReadWrite::receive`] should handle an inbound buffer written via
+/// [`ReadWrite::write`] that is larger than [`MTU_MAX`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Oversized {
+    /// Silently drop the buffer. This is indistinguishable from "no data available" and can
+    /// hide real configuration bugs in the bridged transport; kept as the default only for
+    /// backwards compatibility.
+    #[default]
+    Drop,
+    /// Drop the buffer, but record its size so the application can notice and report it via
+    /// [`ReadWrite::take_dropped_oversized`].
+    Report,
+    /// Split the buffer into [`MTU_MAX`]-sized chunks, delivered across successive
+    /// [`ReadWrite::receive`] calls as [`PacketReceived::Partial`] followed by a final
+    /// [`PacketReceived::Complete`], for ENet to reassemble.
+    Fragment,
+}
+
 /// Provides a Read/Write interface for use with [`Host`](`crate::Host`).
 ///
 /// This provides a useful alternative to implementing the [`Socket`] trait, especially when
@@ -30,6 +48,9 @@ pub struct ReadWrite<A: Address, E: SocketError> {
     inbound: VecDeque<(A, Vec<u8>)>,
     outbound: VecDeque<(A, Vec<u8>)>,
     error: Option<E>,
+    oversized: Oversized,
+    dropped_oversized: Option<usize>,
+    pending_fragment: Option<(A, Vec<u8>)>,
 }
 
 impl<A: Address, E: SocketError> ReadWrite<A, E> {
@@ -53,6 +74,38 @@ impl<A: Address, E: SocketError> ReadWrite<A, E> {
     pub fn error(&mut self, error: E) {
         self.error = Some(error);
     }
+
+    /// Set how [`receive`](`Self::receive`) should handle buffers larger than [`MTU_MAX`].
+    /// Defaults to [`Oversized::Drop`].
+    pub fn set_oversized_policy(&mut self, policy: Oversized) {
+        self.oversized = policy;
+    }
+
+    /// The size of the most recent oversized buffer dropped under
+    /// [`Oversized::Report`], if any has been dropped since the last call.
+    pub fn take_dropped_oversized(&mut self) -> Option<usize> {
+        self.dropped_oversized.take()
+    }
+
+    /// Deliver the next [`MTU_MAX`]-sized chunk of `remainder`, stashing whatever is left
+    /// over as [`Self::pending_fragment`] if this isn't the last chunk.
+    fn deliver_fragment(
+        &mut self,
+        address: A,
+        mut remainder: Vec<u8>,
+        buffer: &mut [u8; MTU_MAX],
+    ) -> (A, PacketReceived) {
+        if remainder.len() <= MTU_MAX {
+            let bytes = remainder.len();
+            copy_into(buffer, &remainder);
+            (address, PacketReceived::Complete(bytes))
+        } else {
+            let rest = remainder.split_off(MTU_MAX);
+            copy_into(buffer, &remainder);
+            self.pending_fragment = Some((address.clone(), rest));
+            (address, PacketReceived::Partial(MTU_MAX))
+        }
+    }
 }
 
 impl<A: Address, E: SocketError> Default for ReadWrite<A, E> {
@@ -61,10 +114,27 @@ impl<A: Address, E: SocketError> Default for ReadWrite<A, E> {
             inbound: VecDeque::new(),
             outbound: VecDeque::new(),
             error: None,
+            oversized: Oversized::default(),
+            dropped_oversized: None,
+            pending_fragment: None,
         }
     }
 }
 
+fn copy_into(buffer: &mut [u8; MTU_MAX], bytes: &[u8]) {
+    #[cfg(feature = "std")]
+    {
+        use std::io::{copy, Cursor};
+        copy(&mut Cursor::new(bytes), &mut Cursor::new(&mut buffer[..]))
+            .expect("Buffer copy should not fail.");
+    }
+    #[cfg(not(feature = "std"))]
+    unsafe {
+        use core::ptr::copy_nonoverlapping;
+        copy_nonoverlapping(bytes.as_ptr(), buffer.as_mut_ptr(), bytes.len());
+    }
+}
+
 impl<A: Address + 'static, E: SocketError> Socket for ReadWrite<A, E> {
     type Address = A;
     type Error = E;
@@ -81,27 +151,27 @@ impl<A: Address + 'static, E: SocketError> Socket for ReadWrite<A, E> {
 
     fn receive(&mut self, buffer: &mut [u8; MTU_MAX]) -> Result<Option<(A, PacketReceived)>, E> {
         if let Some(error) = self.error.take() {
-            Err(error)
-        } else if let Some((address, inbound)) = self.inbound.pop_front() {
-            let bytes = inbound.len();
-            if bytes <= MTU_MAX {
-                #[cfg(feature = "std")]
-                {
-                    use std::io::{copy, Cursor};
-                    copy(&mut Cursor::new(inbound), &mut Cursor::new(&mut buffer[..]))
-                        .expect("Buffer copy should not fail.");
-                }
-                #[cfg(not(feature = "std"))]
-                unsafe {
-                    use core::ptr::copy_nonoverlapping;
-                    copy_nonoverlapping(inbound.as_ptr(), buffer.as_mut_ptr(), bytes);
+            return Err(error);
+        }
+        if let Some((address, remainder)) = self.pending_fragment.take() {
+            return Ok(Some(self.deliver_fragment(address, remainder, buffer)));
+        }
+        let Some((address, inbound)) = self.inbound.pop_front() else {
+            return Ok(None);
+        };
+        let bytes = inbound.len();
+        if bytes <= MTU_MAX {
+            copy_into(buffer, &inbound);
+            Ok(Some((address, PacketReceived::Complete(bytes))))
+        } else {
+            match self.oversized {
+                Oversized::Drop => Ok(None),
+                Oversized::Report => {
+                    self.dropped_oversized = Some(bytes);
+                    Ok(None)
                 }
-                Ok(Some((address, PacketReceived::Complete(bytes))))
-            } else {
-                Ok(None)
+                Oversized::Fragment => Ok(Some(self.deliver_fragment(address, inbound, buffer))),
             }
-        } else {
-            Ok(None)
         }
     }
 }
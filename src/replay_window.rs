@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+
+use crate::PeerID;
+
+/// Word size backing the replay bitmap: a full native register on each target width.
+#[cfg(target_pointer_width = "64")]
+type Word = u64;
+#[cfg(not(target_pointer_width = "64"))]
+type Word = u32;
+
+#[cfg(target_pointer_width = "64")]
+const SHIFT: u32 = 6;
+#[cfg(not(target_pointer_width = "64"))]
+const SHIFT: u32 = 5;
+
+const BITS_PER_WORD: u32 = Word::BITS;
+
+/// Total bits tracked by the bitmap. The effective window is slightly smaller, since the
+/// word currently being written to is excluded to keep wrap-around clearing cheap.
+const BITMAP_BITLEN: u32 = 2048;
+const BITMAP_LEN: usize = (BITMAP_BITLEN / BITS_PER_WORD) as usize;
+const WINDOW_SIZE: u64 = (BITMAP_BITLEN - BITS_PER_WORD) as u64;
+
+/// Per-peer sliding-window replay filter for unsequenced (unreliable) channels.
+///
+/// Implements the bitmap algorithm from [RFC 6479](https://www.rfc-editor.org/rfc/rfc6479),
+/// giving deterministic, constant-memory duplicate suppression without tracking full
+/// sequence histories. Kept per [`PeerID`] inside a [`ReplayFilters`] set.
+#[derive(Debug, Clone)]
+pub struct ReplayWindow {
+    bitmap: [Word; BITMAP_LEN],
+    max: Option<u64>,
+}
+
+impl ReplayWindow {
+    /// Create an empty replay window that has not yet seen any sequence number.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record `seq`, returning `true` if it should be accepted and `false` if it is a
+    /// duplicate or too old relative to the highest sequence number seen so far.
+    ///
+    /// A dropped (`false`) sequence number must not produce a
+    /// [`Receive`](`crate::Event::Receive`) event.
+    pub fn accept(&mut self, seq: u64) -> bool {
+        match self.max {
+            None => {
+                self.set_bit(seq);
+                self.max = Some(seq);
+                true
+            }
+            Some(max) if seq > max => {
+                self.advance(max, seq);
+                self.set_bit(seq);
+                self.max = Some(seq);
+                true
+            }
+            Some(max) if max - seq > WINDOW_SIZE => false,
+            Some(_) => {
+                if self.test_bit(seq) {
+                    false
+                } else {
+                    self.set_bit(seq);
+                    true
+                }
+            }
+        }
+    }
+
+    /// Clear every word made newly visible by the window sliding from `old_max` to
+    /// `new_max`, so a bit from a previous lap of the bitmap can never be mistaken for one
+    /// belonging to the new window.
+    fn advance(&mut self, old_max: u64, new_max: u64) {
+        if (new_max >> SHIFT) - (old_max >> SHIFT) >= BITMAP_LEN as u64 {
+            self.bitmap = [0; BITMAP_LEN];
+            return;
+        }
+        let mut index = Self::word_index(old_max);
+        let new_index = Self::word_index(new_max);
+        while index != new_index {
+            index = (index + 1) & (BITMAP_LEN - 1);
+            self.bitmap[index] = 0;
+        }
+    }
+
+    fn word_index(seq: u64) -> usize {
+        (seq >> SHIFT) as usize & (BITMAP_LEN - 1)
+    }
+
+    fn set_bit(&mut self, seq: u64) {
+        let index = Self::word_index(seq);
+        self.bitmap[index] |= 1 << (seq & (BITS_PER_WORD as u64 - 1));
+    }
+
+    fn test_bit(&self, seq: u64) -> bool {
+        let index = Self::word_index(seq);
+        self.bitmap[index] & (1 << (seq & (BITS_PER_WORD as u64 - 1))) != 0
+    }
+}
+
+impl Default for ReplayWindow {
+    fn default() -> Self {
+        Self {
+            bitmap: [0; BITMAP_LEN],
+            max: None,
+        }
+    }
+}
+
+/// Owns one [`ReplayWindow`] per connected [`PeerID`], the per-peer map this replay filter
+/// needs to be usable at all: sequence numbers from different peers must never be checked
+/// against the same window.
+///
+/// Intended to be owned by a [`Host`](`crate::Host`), behind a `HostSettings` toggle, for as
+/// long as replay protection is enabled. For every inbound unsequenced-channel datagram, the
+/// integration must call [`ReplayFilters::check`] with the peer and the sequence number
+/// carried by ENet's unsequenced command header *before* constructing the corresponding
+/// [`Receive`](`crate::Event::Receive`) event: a rejected sequence number must never reach
+/// the application as an event. This type only provides the per-peer bookkeeping; it does
+/// not perform protection on its own until something calls [`ReplayFilters::check`] at that
+/// point in the receive path.
+#[derive(Debug, Clone, Default)]
+pub struct ReplayFilters {
+    windows: HashMap<PeerID, ReplayWindow>,
+}
+
+impl ReplayFilters {
+    /// Create an empty set of per-peer replay windows.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check `seq` against `peer`'s replay window, creating one if this is the first
+    /// sequence number seen from `peer`. Returns `true` if `seq` should be accepted.
+    pub fn check(&mut self, peer: PeerID, seq: u64) -> bool {
+        self.windows.entry(peer).or_default().accept(seq)
+    }
+
+    /// Drop the replay window kept for `peer`, e.g. once it disconnects.
+    pub fn remove(&mut self, peer: PeerID) {
+        self.windows.remove(&peer);
+    }
+}
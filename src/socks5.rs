@@ -0,0 +1,268 @@
+use std::io::{self, Read, Write};
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, TcpStream, UdpSocket};
+
+use crate::{PacketReceived, Socket, SocketError, SocketOptions, MTU_MAX};
+
+const SOCKS_VERSION: u8 = 0x05;
+const AUTH_NONE: u8 = 0x00;
+const CMD_UDP_ASSOCIATE: u8 = 0x03;
+const ATYP_IPV4: u8 = 0x01;
+const ATYP_IPV6: u8 = 0x04;
+const RSV: [u8; 2] = [0x00, 0x00];
+const FRAG_UNFRAGMENTED: u8 = 0x00;
+
+/// Size of the largest SOCKS5 UDP request header: reserved bytes, fragment octet, address
+/// type, an IPv6 address, and a port (4 + 16 + 2). An IPv4 header is smaller but every
+/// buffer sized to receive a relay datagram must assume the larger of the two.
+const UDP_REQUEST_HEADER_MAX_LEN: usize = 4 + 16 + 2;
+
+/// Error surfaced by [`Socks5Socket`], covering both transport I/O failures and the proxy
+/// refusing the request.
+#[derive(Debug)]
+pub enum Socks5Error {
+    /// The control (TCP) or relay (UDP) connection failed.
+    Io(io::Error),
+    /// The proxy rejected the SOCKS5 handshake or `UDP ASSOCIATE` request. For a refused
+    /// handshake, `reply_code` is the authentication method the proxy selected instead of
+    /// "no authentication"; for a refused `UDP ASSOCIATE`, it is the code from the SOCKS5
+    /// reply, per [RFC 1928 section 6](https://www.rfc-editor.org/rfc/rfc1928#section-6).
+    ProxyRefused { reply_code: u8 },
+    /// The proxy's reply did not follow the SOCKS5 wire format.
+    MalformedReply,
+}
+
+impl From<io::Error> for Socks5Error {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl core::fmt::Display for Socks5Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "SOCKS5 transport error: {error}"),
+            Self::ProxyRefused { reply_code } => {
+                write!(
+                    f,
+                    "SOCKS5 proxy refused request (reply code {reply_code:#04x})"
+                )
+            }
+            Self::MalformedReply => write!(f, "SOCKS5 proxy sent a malformed reply"),
+        }
+    }
+}
+
+impl std::error::Error for Socks5Error {}
+impl SocketError for Socks5Error {}
+
+/// The established half of a [`Socks5Socket`]: the open TCP control connection and the UDP
+/// relay it negotiated.
+#[derive(Debug)]
+struct Established {
+    _control: TcpStream,
+    relay: UdpSocket,
+    relay_addr: SocketAddr,
+}
+
+/// A [`Socket`] that tunnels ENet traffic through a SOCKS5 proxy's `UDP ASSOCIATE`, per
+/// [RFC 1928](https://www.rfc-editor.org/rfc/rfc1928).
+///
+/// Holds open the TCP control connection used to establish the association for as long as
+/// the relay is needed (most SOCKS5 servers tear the association down when it closes), and
+/// prepends/strips the SOCKS5 UDP request header around every ENet datagram sent or received
+/// on the relay's UDP endpoint.
+///
+/// The handshake itself runs in [`Socket::init`], not in the constructor, so a proxy that
+/// refuses the connection or the `UDP ASSOCIATE` request surfaces through
+/// [`Host::new`](`crate::Host::new`) like any other socket initialization failure, rather
+/// than needing to be handled separately before the socket is ever handed to a [`Host`](`crate::Host`).
+#[derive(Debug)]
+pub struct Socks5Socket {
+    proxy_addr: SocketAddr,
+    established: Option<Established>,
+}
+
+impl Socks5Socket {
+    /// Create a socket that will perform the SOCKS5 handshake with `proxy_addr` and
+    /// establish a `UDP ASSOCIATE` relay the first time [`Socket::init`] runs.
+    #[must_use]
+    pub fn new(proxy_addr: SocketAddr) -> Self {
+        Self {
+            proxy_addr,
+            established: None,
+        }
+    }
+
+    /// The established relay, or panics if [`Socket::init`] has not yet run.
+    ///
+    /// `Host` always calls [`Socket::init`] once before using a socket, so this never fires
+    /// in practice.
+    fn established(&mut self) -> &mut Established {
+        self.established
+            .as_mut()
+            .expect("Socks5Socket::init must run before send/receive")
+    }
+
+    /// Perform the SOCKS5 handshake and establish the `UDP ASSOCIATE` relay.
+    fn handshake(proxy_addr: SocketAddr) -> Result<Established, Socks5Error> {
+        let mut control = TcpStream::connect(proxy_addr)?;
+
+        // Greeting: version 5, one method offered (no auth).
+        control.write_all(&[SOCKS_VERSION, 0x01, AUTH_NONE])?;
+        let mut greeting_reply = [0u8; 2];
+        control.read_exact(&mut greeting_reply)?;
+        if greeting_reply[0] != SOCKS_VERSION {
+            return Err(Socks5Error::MalformedReply);
+        }
+        if greeting_reply[1] != AUTH_NONE {
+            return Err(Socks5Error::ProxyRefused {
+                reply_code: greeting_reply[1],
+            });
+        }
+
+        // UDP ASSOCIATE request. We don't yet know our outbound address, so we ask the
+        // proxy to accept datagrams from any port on this connection's source address.
+        let mut request = vec![SOCKS_VERSION, CMD_UDP_ASSOCIATE, 0x00, ATYP_IPV4];
+        request.extend_from_slice(&Ipv4Addr::UNSPECIFIED.octets());
+        request.extend_from_slice(&0u16.to_be_bytes());
+        control.write_all(&request)?;
+
+        let relay_addr = read_socks5_reply(&mut control)?;
+
+        let relay = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+        relay.connect(relay_addr)?;
+        // `receive` treats `WouldBlock` as "no datagram pending" the way `Host::service`'s
+        // poll loop expects; without this, a relay with nothing to read would block the
+        // poll loop indefinitely instead of returning `Ok(None)`.
+        relay.set_nonblocking(true)?;
+
+        Ok(Established {
+            _control: control,
+            relay,
+            relay_addr,
+        })
+    }
+}
+
+/// Read a SOCKS5 reply (shared shape for the `UDP ASSOCIATE` reply), returning the bound
+/// address the proxy reports for the relay.
+fn read_socks5_reply(control: &mut TcpStream) -> Result<SocketAddr, Socks5Error> {
+    let mut header = [0u8; 4];
+    control.read_exact(&mut header)?;
+    let [version, reply_code, _rsv, atyp] = header;
+    if version != SOCKS_VERSION {
+        return Err(Socks5Error::MalformedReply);
+    }
+    if reply_code != 0x00 {
+        return Err(Socks5Error::ProxyRefused { reply_code });
+    }
+    match atyp {
+        ATYP_IPV4 => {
+            let mut addr = [0u8; 4];
+            control.read_exact(&mut addr)?;
+            let mut port = [0u8; 2];
+            control.read_exact(&mut port)?;
+            Ok(SocketAddr::from((addr, u16::from_be_bytes(port))))
+        }
+        ATYP_IPV6 => {
+            let mut addr = [0u8; 16];
+            control.read_exact(&mut addr)?;
+            let mut port = [0u8; 2];
+            control.read_exact(&mut port)?;
+            Ok(SocketAddr::from((addr, u16::from_be_bytes(port))))
+        }
+        _ => Err(Socks5Error::MalformedReply),
+    }
+}
+
+/// Prepend the SOCKS5 UDP request header (reserved bytes, fragment octet, target address
+/// and port) to `payload`, addressed at `target`.
+fn encode_udp_request(target: SocketAddr, payload: &[u8]) -> Vec<u8> {
+    let mut datagram = Vec::with_capacity(UDP_REQUEST_HEADER_MAX_LEN + payload.len());
+    datagram.extend_from_slice(&RSV);
+    datagram.push(FRAG_UNFRAGMENTED);
+    match target {
+        SocketAddr::V4(addr) => {
+            datagram.push(ATYP_IPV4);
+            datagram.extend_from_slice(&addr.ip().octets());
+            datagram.extend_from_slice(&addr.port().to_be_bytes());
+        }
+        SocketAddr::V6(addr) => {
+            datagram.push(ATYP_IPV6);
+            datagram.extend_from_slice(&addr.ip().octets());
+            datagram.extend_from_slice(&addr.port().to_be_bytes());
+        }
+    }
+    datagram.extend_from_slice(payload);
+    datagram
+}
+
+/// Strip the SOCKS5 UDP request header from an inbound relay datagram, returning the
+/// encapsulated target address and the start of the ENet payload within `datagram`.
+fn decode_udp_request(datagram: &[u8]) -> Option<(SocketAddr, usize)> {
+    if datagram.len() < 4 || datagram[3] == 0 {
+        return None;
+    }
+    let atyp = datagram[3];
+    match atyp {
+        ATYP_IPV4 => {
+            let header_len = 4 + 4 + 2;
+            if datagram.len() < header_len {
+                return None;
+            }
+            let ip = Ipv4Addr::new(datagram[4], datagram[5], datagram[6], datagram[7]);
+            let port = u16::from_be_bytes([datagram[8], datagram[9]]);
+            Some((SocketAddr::from((ip, port)), header_len))
+        }
+        ATYP_IPV6 => {
+            let header_len = 4 + 16 + 2;
+            if datagram.len() < header_len {
+                return None;
+            }
+            let mut octets = [0u8; 16];
+            octets.copy_from_slice(&datagram[4..20]);
+            let ip = Ipv6Addr::from(octets);
+            let port = u16::from_be_bytes([datagram[20], datagram[21]]);
+            Some((SocketAddr::from((ip, port)), header_len))
+        }
+        _ => None,
+    }
+}
+
+impl Socket for Socks5Socket {
+    type Address = SocketAddr;
+    type Error = Socks5Error;
+
+    fn init(&mut self, _socket_options: SocketOptions) -> Result<(), Self::Error> {
+        self.established = Some(Self::handshake(self.proxy_addr)?);
+        Ok(())
+    }
+
+    fn send(&mut self, address: SocketAddr, buffer: &[u8]) -> Result<usize, Self::Error> {
+        let datagram = encode_udp_request(address, buffer);
+        let established = self.established();
+        established
+            .relay
+            .send_to(&datagram, established.relay_addr)?;
+        Ok(buffer.len())
+    }
+
+    fn receive(
+        &mut self,
+        buffer: &mut [u8; MTU_MAX],
+    ) -> Result<Option<(SocketAddr, PacketReceived)>, Self::Error> {
+        let mut scratch = [0u8; MTU_MAX + UDP_REQUEST_HEADER_MAX_LEN];
+        match self.established().relay.recv_from(&mut scratch) {
+            Ok((bytes, _from)) => {
+                let Some((target, header_len)) = decode_udp_request(&scratch[..bytes]) else {
+                    return Ok(None);
+                };
+                let payload = bytes - header_len;
+                buffer[..payload].copy_from_slice(&scratch[header_len..bytes]);
+                Ok(Some((target, PacketReceived::Complete(payload))))
+            }
+            Err(error) if error.kind() == io::ErrorKind::WouldBlock => Ok(None),
+            Err(error) => Err(Socks5Error::Io(error)),
+        }
+    }
+}